@@ -1,14 +1,33 @@
 //! Entity extraction from user input
 
 use crate::types::Entity;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use aho_corasick::AhoCorasick;
 use regex::Regex;
 
+/// A registered entity pattern plus the literal atoms that gate it.
+struct PatternEntry {
+    regex: Regex,
+    entity_type: String,
+    // Lowercase literals, at least one of which must appear in the input
+    // for this regex to possibly match. Empty means "always run" (e.g.
+    // plain numbers, which have no distinguishing literal).
+    required_literals: Vec<String>,
+}
+
 /// Extract entities from user input
 ///
-/// Identifies paths, files, numbers, and other structured data.
+/// Identifies paths, files, numbers, and other structured data. Borrows the
+/// FilteredRE2 technique: before running any regex, a single Aho-Corasick
+/// automaton scans the input once for each pattern's required literal
+/// atoms (e.g. `.txt`/`.pdf` for the file-extension regex, `%` for
+/// envvars), so patterns that obviously can't match are skipped.
 pub struct EntityExtractor {
-    patterns: Vec<(Regex, String)>, // (pattern, entity_type)
+    patterns: Vec<PatternEntry>,
+    // Built from `patterns`; `None` when no pattern has a required literal.
+    atom_automaton: Option<AhoCorasick>,
+    // Aho-Corasick pattern index -> entity-pattern indices gated by that atom
+    atom_to_patterns: Vec<Vec<usize>>,
 }
 
 impl EntityExtractor {
@@ -17,25 +36,96 @@ impl EntityExtractor {
         // Note: Rust regex doesn't support lookahead/lookbehind, so we filter after matching
         let patterns = vec![
             // Quoted paths/files
-            (Regex::new(r#""([^"]+)""#).expect("Invalid regex pattern"), "path".to_string()),
-            (Regex::new(r"'([^']+)'").expect("Invalid regex pattern"), "path".to_string()),
+            (Regex::new(r#""([^"]+)""#).expect("Invalid regex pattern"), "path".to_string(), vec!["\"".to_string()]),
+            (Regex::new(r"'([^']+)'").expect("Invalid regex pattern"), "path".to_string(), vec!["'".to_string()]),
             // Environment variables
-            (Regex::new(r"%([A-Z_]+)%").expect("Invalid regex pattern"), "envvar".to_string()),
+            (Regex::new(r"%([A-Z_]+)%").expect("Invalid regex pattern"), "envvar".to_string(), vec!["%".to_string()]),
             // File extensions
-            (Regex::new(r"\b(\w+\.(txt|pdf|doc|docx|jpg|png|log|json|xml|py|exe))\b").expect("Invalid regex pattern"), "file".to_string()),
+            (Regex::new(r"\b(\w+\.(txt|pdf|doc|docx|jpg|png|log|json|xml|py|exe))\b").expect("Invalid regex pattern"), "file".to_string(),
+                vec![".txt".to_string(), ".pdf".to_string(), ".doc".to_string(), ".docx".to_string(), ".jpg".to_string(), ".png".to_string(), ".log".to_string(), ".json".to_string(), ".xml".to_string(), ".py".to_string(), ".exe".to_string()]),
             // Numbers with units (must come before plain numbers)
-            (Regex::new(r"\b(\d+)\s*(gb|mb|kb|percent|%)").expect("Invalid regex pattern"), "number_with_unit".to_string()),
-            // Plain numbers
-            (Regex::new(r"\b(\d+)\b").expect("Invalid regex pattern"), "number".to_string()),
+            (Regex::new(r"\b(\d+)\s*(gb|mb|kb|percent|%)").expect("Invalid regex pattern"), "number_with_unit".to_string(),
+                vec!["gb".to_string(), "mb".to_string(), "kb".to_string(), "percent".to_string(), "%".to_string()]),
+            // Plain numbers - no distinguishing literal, always run
+            (Regex::new(r"\b(\d+)\b").expect("Invalid regex pattern"), "number".to_string(), Vec::new()),
         ];
-        
-        Self { patterns }
+
+        let mut extractor = Self {
+            patterns: Vec::new(),
+            atom_automaton: None,
+            atom_to_patterns: Vec::new(),
+        };
+        for (regex, entity_type, required_literals) in patterns {
+            extractor.add_pattern(regex, entity_type, required_literals);
+        }
+        extractor
     }
-    
+
+    /// Register a custom entity pattern that participates in the same
+    /// literal prefilter as the built-in patterns. `required_literals`
+    /// should be lowercase atoms, any one of which must be present in the
+    /// (lowercased) input for `regex` to be run; pass an empty slice for a
+    /// pattern with no distinguishing literal, which always runs.
+    pub fn add_pattern(&mut self, regex: Regex, entity_type: String, required_literals: Vec<String>) {
+        self.patterns.push(PatternEntry {
+            regex,
+            entity_type,
+            required_literals,
+        });
+        self.rebuild_atom_automaton();
+    }
+
+    fn rebuild_atom_automaton(&mut self) {
+        let mut atom_to_patterns: HashMap<String, Vec<usize>> = HashMap::new();
+        for (idx, entry) in self.patterns.iter().enumerate() {
+            for literal in &entry.required_literals {
+                atom_to_patterns.entry(literal.clone()).or_default().push(idx);
+            }
+        }
+
+        if atom_to_patterns.is_empty() {
+            self.atom_automaton = None;
+            self.atom_to_patterns = Vec::new();
+            return;
+        }
+
+        let atoms: Vec<&str> = atom_to_patterns.keys().map(String::as_str).collect();
+        self.atom_to_patterns = atoms
+            .iter()
+            .map(|atom| atom_to_patterns[*atom].clone())
+            .collect();
+        self.atom_automaton = Some(AhoCorasick::new(&atoms).expect("atoms are plain literal strings"));
+    }
+
+    /// Which registered pattern indices are worth running against the
+    /// (already lowercased) input: those whose required literal appears,
+    /// plus every pattern with no required literal.
+    fn satisfied_patterns(&self, text_lower: &str) -> HashSet<usize> {
+        let mut satisfied: HashSet<usize> = self
+            .patterns
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.required_literals.is_empty())
+            .map(|(idx, _)| idx)
+            .collect();
+
+        if let Some(automaton) = &self.atom_automaton {
+            // Overlapping search, so an atom overlapping an earlier hit is
+            // still reported.
+            for m in automaton.find_overlapping_iter(text_lower) {
+                for &idx in &self.atom_to_patterns[m.pattern().as_usize()] {
+                    satisfied.insert(idx);
+                }
+            }
+        }
+
+        satisfied
+    }
+
     pub fn extract(&self, text: &str) -> Vec<Entity> {
         let mut entities = Vec::new();
         let text_lower = text.to_lowercase();
-        
+
         // Check for special path references
         let special_paths: HashMap<&str, &str> = [
             ("desktop", "Desktop"),
@@ -49,7 +139,7 @@ impl EntityExtractor {
         .iter()
         .cloned()
         .collect();
-        
+
         for (path_name, _) in &special_paths {
             if let Some(start) = text_lower.find(path_name) {
                 entities.push(Entity::new(
@@ -61,12 +151,19 @@ impl EntityExtractor {
                 ));
             }
         }
-        
-        // Extract using regex patterns
+
+        // Extract using regex patterns that passed the literal prefilter
         // Track positions of number_with_unit matches to avoid duplicates
         let mut number_with_unit_positions = std::collections::HashSet::new();
-        
-        for (pattern, entity_type) in &self.patterns {
+
+        let satisfied = self.satisfied_patterns(&text_lower);
+
+        for (idx, entry) in self.patterns.iter().enumerate() {
+            if !satisfied.contains(&idx) {
+                continue;
+            }
+            let pattern = &entry.regex;
+            let entity_type = &entry.entity_type;
             for cap in pattern.captures_iter(text) {
                 if let Some(matched) = cap.get(0) {
                     let start = matched.start();
@@ -137,8 +234,36 @@ mod tests {
     fn test_extract_path() {
         let extractor = EntityExtractor::new();
         let entities = extractor.extract(r#"open "C:\Users\Desktop""#);
-        
+
         assert!(!entities.is_empty());
         assert!(entities.iter().any(|e| e.entity_type == "path"));
     }
+
+    #[test]
+    fn test_prefilter_skips_patterns_without_required_literal() {
+        let extractor = EntityExtractor::new();
+        // No quotes, no '%', no file extension, no unit: only the plain
+        // number regex (which has no required literal) should run.
+        let entities = extractor.extract("free up 5");
+
+        assert!(entities.iter().any(|e| e.entity_type == "number" && e.value == "5"));
+        assert!(!entities.iter().any(|e| e.entity_type == "envvar"));
+        assert!(!entities.iter().any(|e| e.entity_type == "path"));
+    }
+
+    #[test]
+    fn test_add_pattern_participates_in_prefilter() {
+        let mut extractor = EntityExtractor::new();
+        extractor.add_pattern(
+            Regex::new(r"\b(ticket-\d+)\b").unwrap(),
+            "ticket".to_string(),
+            vec!["ticket-".to_string()],
+        );
+
+        let entities = extractor.extract("see ticket-42 for details");
+        assert!(entities.iter().any(|e| e.entity_type == "ticket" && e.value == "ticket-42"));
+
+        let no_match = extractor.extract("nothing relevant here");
+        assert!(!no_match.iter().any(|e| e.entity_type == "ticket"));
+    }
 }
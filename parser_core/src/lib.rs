@@ -7,11 +7,13 @@ pub mod types;
 pub mod similarity;
 pub mod matcher;
 pub mod entities;
+pub mod linter;
 
 pub use types::*;
 pub use similarity::*;
 pub use matcher::*;
 pub use entities::*;
+pub use linter::*;
 
 // Python bindings
 #[cfg(feature = "extension-module")]
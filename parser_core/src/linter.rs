@@ -0,0 +1,312 @@
+//! Trigger-set linter - static diagnostics for conflicting/redundant triggers
+//!
+//! As a provider accumulates triggers, silent conflicts creep in: two
+//! triggers with effectively the same pattern, a high-weight generic
+//! pattern that always outscores a more specific one, or an alias shared
+//! between triggers that route to different intents. `lint_triggers`
+//! surfaces these the way match-arm diagnostics surface unreachable or
+//! overlapping patterns, so tooling can warn at registration time instead
+//! of a user silently always getting the wrong intent.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::similarity::calculate_similarity;
+use crate::types::Trigger;
+
+/// How serious a `TriggerDiagnostic` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// What kind of conflict a `TriggerDiagnostic` reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// Two triggers are identical (pattern + aliases) after normalization.
+    Redundant,
+    /// A trigger's best achievable weighted score is dominated on every
+    /// sampled input, by one other trigger or by different triggers per
+    /// sample, so it can never win on those inputs.
+    Unreachable,
+    /// An alias is shared between triggers that route to different intents.
+    OverlappingAlias,
+}
+
+/// A single diagnostic produced by [`lint_triggers`].
+#[derive(Debug, Clone)]
+pub struct TriggerDiagnostic {
+    pub kind: DiagnosticKind,
+    pub severity: Severity,
+    /// Indices into the `triggers` slice passed to `lint_triggers`.
+    pub trigger_indices: Vec<usize>,
+    pub message: String,
+}
+
+/// Lint a trigger set for redundant, unreachable, and overlapping-alias
+/// conflicts.
+pub fn lint_triggers(triggers: &[Trigger]) -> Vec<TriggerDiagnostic> {
+    let mut diagnostics = Vec::new();
+    diagnostics.extend(find_redundant(triggers));
+    diagnostics.extend(find_overlapping_aliases(triggers));
+    diagnostics.extend(find_unreachable(triggers));
+    diagnostics
+}
+
+/// Normalized `(pattern, sorted aliases)` signature used to detect
+/// duplicate triggers regardless of casing/whitespace/alias order.
+fn normalized_signature(trigger: &Trigger) -> (String, Vec<String>) {
+    let pattern = trigger.pattern.trim().to_lowercase();
+    let mut aliases: Vec<String> = trigger
+        .aliases
+        .iter()
+        .map(|alias| alias.trim().to_lowercase())
+        .collect();
+    aliases.sort();
+    (pattern, aliases)
+}
+
+fn find_redundant(triggers: &[Trigger]) -> Vec<TriggerDiagnostic> {
+    let mut seen: HashMap<(String, Vec<String>), usize> = HashMap::new();
+    let mut diagnostics = Vec::new();
+
+    for (idx, trigger) in triggers.iter().enumerate() {
+        let signature = normalized_signature(trigger);
+        if let Some(&first_idx) = seen.get(&signature) {
+            diagnostics.push(TriggerDiagnostic {
+                kind: DiagnosticKind::Redundant,
+                severity: Severity::Error,
+                trigger_indices: vec![first_idx, idx],
+                message: format!(
+                    "Trigger {} (\"{}\") is redundant with trigger {} (\"{}\") - identical pattern/aliases after normalization",
+                    idx, trigger.pattern, first_idx, triggers[first_idx].pattern
+                ),
+            });
+        } else {
+            seen.insert(signature, idx);
+        }
+    }
+
+    diagnostics
+}
+
+fn find_overlapping_aliases(triggers: &[Trigger]) -> Vec<TriggerDiagnostic> {
+    let mut alias_owners: HashMap<String, Vec<usize>> = HashMap::new();
+
+    for (idx, trigger) in triggers.iter().enumerate() {
+        for alias in &trigger.aliases {
+            alias_owners
+                .entry(alias.trim().to_lowercase())
+                .or_default()
+                .push(idx);
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    for (alias, owners) in &alias_owners {
+        let intents: HashSet<&str> = owners
+            .iter()
+            .map(|&idx| triggers[idx].intent_name.as_str())
+            .collect();
+
+        if intents.len() > 1 {
+            let mut intents: Vec<&str> = intents.into_iter().collect();
+            intents.sort();
+            diagnostics.push(TriggerDiagnostic {
+                kind: DiagnosticKind::OverlappingAlias,
+                severity: Severity::Warning,
+                trigger_indices: owners.clone(),
+                message: format!(
+                    "Alias \"{}\" is shared by triggers routing to different intents: {}",
+                    alias,
+                    intents.join(", ")
+                ),
+            });
+        }
+    }
+
+    diagnostics
+}
+
+/// Weighted score of `other` against `input` (lowercase), checking both its
+/// pattern and its aliases - mirrors the scoring `match_intent` performs.
+fn weighted_score(input_lower: &str, other: &Trigger) -> f64 {
+    let mut best = calculate_similarity(input_lower, &other.pattern.to_lowercase());
+    for alias in &other.aliases {
+        let score = calculate_similarity(input_lower, &alias.to_lowercase());
+        if score > best {
+            best = score;
+        }
+    }
+    best * other.weight
+}
+
+fn find_unreachable(triggers: &[Trigger]) -> Vec<TriggerDiagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for (idx, trigger) in triggers.iter().enumerate() {
+        // Sample inputs derived from the trigger's own pattern and
+        // aliases: the inputs it's most likely to be invoked by.
+        let sample_inputs: Vec<String> = std::iter::once(trigger.pattern.clone())
+            .chain(trigger.aliases.iter().cloned())
+            .map(|s| s.to_lowercase())
+            .collect();
+
+        if sample_inputs.is_empty() {
+            continue;
+        }
+
+        // Track the dominating trigger *per sample* rather than
+        // overwriting a single running value: if sample 1 is dominated by
+        // trigger B and sample 2 by trigger C, the right diagnostic names
+        // both, not whichever one happened to be scored last.
+        let mut dominators_per_sample: Vec<Option<usize>> = Vec::with_capacity(sample_inputs.len());
+
+        for input in &sample_inputs {
+            let own_score = weighted_score(input, trigger);
+
+            let best_other = triggers
+                .iter()
+                .enumerate()
+                .filter(|(other_idx, _)| *other_idx != idx)
+                .map(|(other_idx, other)| (other_idx, weighted_score(input, other)))
+                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+            let sample_dominator = match best_other {
+                Some((other_idx, other_score)) if other_score > own_score => Some(other_idx),
+                _ => None,
+            };
+            dominators_per_sample.push(sample_dominator);
+        }
+
+        // Only flag the trigger if every sample was dominated by *something*.
+        if dominators_per_sample.iter().any(Option::is_none) {
+            continue;
+        }
+
+        let mut distinct_dominators: Vec<usize> = dominators_per_sample
+            .into_iter()
+            .map(|d| d.expect("checked above"))
+            .collect();
+        distinct_dominators.sort_unstable();
+        distinct_dominators.dedup();
+
+        let message = match distinct_dominators.as_slice() {
+            [other_idx] => format!(
+                "Trigger {} (\"{}\") is dominated by trigger {} (\"{}\") on every one of its own sample inputs and may never win",
+                idx, trigger.pattern, other_idx, triggers[*other_idx].pattern
+            ),
+            others => format!(
+                "Trigger {} (\"{}\") is dominated on every one of its own sample inputs, but by different triggers depending on the sample ({}) - no single trigger is the culprit",
+                idx,
+                trigger.pattern,
+                others
+                    .iter()
+                    .map(|other_idx| format!("{} (\"{}\")", other_idx, triggers[*other_idx].pattern))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+
+        let mut trigger_indices = vec![idx];
+        trigger_indices.extend(distinct_dominators);
+
+        diagnostics.push(TriggerDiagnostic {
+            kind: DiagnosticKind::Unreachable,
+            severity: Severity::Warning,
+            trigger_indices,
+            message,
+        });
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trigger(pattern: &str, intent: &str, weight: f64, aliases: &[&str]) -> Trigger {
+        Trigger {
+            pattern: pattern.to_string(),
+            intent_name: intent.to_string(),
+            provider_name: "test".to_string(),
+            weight,
+            aliases: aliases.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_detects_redundant_triggers() {
+        let triggers = vec![
+            trigger("open desktop", "open_desktop", 1.0, &[]),
+            trigger("Open Desktop", "open_desktop_dup", 1.0, &[]),
+        ];
+
+        let diagnostics = lint_triggers(&triggers);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.kind == DiagnosticKind::Redundant && d.trigger_indices == vec![0, 1]));
+    }
+
+    #[test]
+    fn test_detects_overlapping_alias() {
+        let triggers = vec![
+            trigger("open desktop", "open_desktop", 1.0, &["show me"]),
+            trigger("open downloads", "open_downloads", 1.0, &["show me"]),
+        ];
+
+        let diagnostics = lint_triggers(&triggers);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.kind == DiagnosticKind::OverlappingAlias));
+    }
+
+    #[test]
+    fn test_detects_unreachable_trigger() {
+        let triggers = vec![
+            // Generic, high-weight catch-all that always outscores the
+            // specific trigger below on every one of its sample inputs.
+            trigger("open", "open_anything", 1.0, &[]),
+            trigger("open desktop", "open_desktop", 0.5, &[]),
+        ];
+
+        let diagnostics = lint_triggers(&triggers);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.kind == DiagnosticKind::Unreachable && d.trigger_indices[0] == 1));
+    }
+
+    #[test]
+    fn test_unreachable_with_mixed_dominators_names_all_of_them() {
+        // Trigger 0's pattern sample is dominated by trigger 1, but its
+        // alias sample is dominated by trigger 2 instead - no single
+        // trigger dominates every sample, so the diagnostic must name both
+        // rather than just whichever was scored last.
+        let triggers = vec![
+            trigger("open desktop", "open_desktop", 0.3, &["show desktop"]),
+            trigger("open desktop now please", "open_anything", 1.0, &[]),
+            trigger("show desktop immediately", "show_anything", 1.0, &[]),
+        ];
+
+        let diagnostics = lint_triggers(&triggers);
+        let diag = diagnostics
+            .iter()
+            .find(|d| d.kind == DiagnosticKind::Unreachable && d.trigger_indices[0] == 0)
+            .expect("expected an unreachable diagnostic for trigger 0");
+
+        assert!(diag.trigger_indices.contains(&1));
+        assert!(diag.trigger_indices.contains(&2));
+    }
+
+    #[test]
+    fn test_no_diagnostics_for_distinct_triggers() {
+        let triggers = vec![
+            trigger("open desktop", "open_desktop", 1.0, &[]),
+            trigger("list processes", "list_processes", 1.0, &[]),
+        ];
+
+        let diagnostics = lint_triggers(&triggers);
+        assert!(diagnostics.is_empty());
+    }
+}
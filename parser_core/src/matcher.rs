@@ -1,7 +1,11 @@
 //! Intent matcher - Fast matching of user input against trigger patterns
 
-use crate::types::{IntentMatch, Trigger, ParseResult, AmbiguousMatch};
-use crate::similarity::calculate_similarity;
+use std::collections::{HashMap, HashSet};
+
+use aho_corasick::AhoCorasick;
+
+use crate::types::{IntentMatch, Trigger, ParseResult, AmbiguousMatch, Suggestion};
+use crate::similarity::{calculate_similarity, jaro_winkler, match_positions};
 
 /// Confidence thresholds for matching
 pub const CONFIDENCE_THRESHOLD: f64 = 0.90; // Very high confidence: execute directly
@@ -9,39 +13,187 @@ pub const MIN_CONFIDENCE: f64 = 0.50; // Minimum for rule-based
 pub const AMBIGUITY_ZONE_START: f64 = 0.60;
 pub const AMBIGUITY_ZONE_END: f64 = 0.90;
 
+/// Minimum Jaro-Winkler score for a trigger to be offered as a "did you
+/// mean ...?" suggestion.
+const SUGGESTION_CONFIDENCE: f64 = 0.7;
+/// Cap on how many suggestions to surface at once.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Shortest literal worth indexing; anything shorter is too common to narrow
+/// the candidate set and is left to the full scan fallback.
+const MIN_LITERAL_LEN: usize = 3;
+
+/// Aho-Corasick prefilter over trigger/alias literals.
+///
+/// `match_intent` alone scores every trigger against the input, which is
+/// fine for dozens of triggers but doesn't hold up once a crate user
+/// registers thousands of them. `TriggerPrefilter` tokenizes each trigger's
+/// pattern and aliases into required keyword literals and builds a single
+/// automaton mapping each literal back to the trigger indices that contain
+/// it, so matching can narrow to a candidate set before running the
+/// expensive similarity scoring.
+pub struct TriggerPrefilter {
+    automaton: Option<AhoCorasick>,
+    // literal pattern index (as assigned to `automaton`) -> trigger indices
+    literal_triggers: Vec<Vec<usize>>,
+    // triggers with no extractable literal always participate, since the
+    // automaton can't rule them out
+    always_candidates: HashSet<usize>,
+}
+
+impl TriggerPrefilter {
+    /// Build a prefilter over `triggers`. Call again whenever the trigger
+    /// set changes (see `PyIntentMatcher`, which rebuilds lazily).
+    pub fn build(triggers: &[Trigger]) -> Self {
+        let mut literal_to_triggers: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut always_candidates = HashSet::new();
+
+        for (idx, trigger) in triggers.iter().enumerate() {
+            let mut tokens = tokenize(&trigger.pattern);
+            for alias in &trigger.aliases {
+                tokens.extend(tokenize(alias));
+            }
+
+            if tokens.is_empty() {
+                always_candidates.insert(idx);
+                continue;
+            }
+
+            // A trigger with any token too short to index (e.g. "cd" in
+            // "cd projects") can't be safely excluded by the automaton: the
+            // full scan would still score it, so the prefilter must not be
+            // the one diverging from that. Fall back to always scoring it,
+            // alongside whichever longer tokens it does have.
+            if tokens.iter().any(|token| token.len() < MIN_LITERAL_LEN) {
+                always_candidates.insert(idx);
+            }
+
+            for literal in tokens.into_iter().filter(|token| token.len() >= MIN_LITERAL_LEN) {
+                literal_to_triggers.entry(literal).or_default().push(idx);
+            }
+        }
+
+        if literal_to_triggers.is_empty() {
+            return Self {
+                automaton: None,
+                literal_triggers: Vec::new(),
+                always_candidates,
+            };
+        }
+
+        let literals: Vec<&str> = literal_to_triggers.keys().map(String::as_str).collect();
+        let literal_triggers: Vec<Vec<usize>> = literals
+            .iter()
+            .map(|literal| literal_to_triggers[*literal].clone())
+            .collect();
+        let automaton = AhoCorasick::new(&literals).expect("literals are plain keyword strings");
+
+        Self {
+            automaton: Some(automaton),
+            literal_triggers,
+            always_candidates,
+        }
+    }
+
+    /// Return the set of trigger indices worth scoring against
+    /// `input_normalized` (already lowercased/trimmed).
+    pub fn candidates(&self, input_normalized: &str) -> HashSet<usize> {
+        let mut candidates = self.always_candidates.clone();
+
+        if let Some(automaton) = &self.automaton {
+            // Overlapping search, so a literal nested in or overlapping an
+            // earlier hit (e.g. "desktop" after "desk") is still reported.
+            for m in automaton.find_overlapping_iter(input_normalized) {
+                for &idx in &self.literal_triggers[m.pattern().as_usize()] {
+                    candidates.insert(idx);
+                }
+            }
+        }
+
+        candidates
+    }
+}
+
+/// Tokenize `text` into lowercase alphanumeric tokens, unfiltered by length.
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_string())
+        .collect()
+}
+
 /// Match user input against a list of triggers
 ///
 /// Returns the best match, ambiguous matches, or none if no good match found.
 pub fn match_intent(input: &str, triggers: &[Trigger]) -> ParseResult {
+    match_intent_over(input, triggers, 0..triggers.len())
+}
+
+/// Match user input against `triggers`, scoring only the candidates that
+/// `prefilter` judges reachable from `input`. Falls back to a full scan
+/// whenever the prefilter has no automaton (e.g. every trigger lacked an
+/// extractable literal).
+pub fn match_intent_prefiltered(
+    input: &str,
+    triggers: &[Trigger],
+    prefilter: &TriggerPrefilter,
+) -> ParseResult {
     if triggers.is_empty() {
         return ParseResult::None;
     }
-    
-    // Normalize input
-    let input_normalized = input.to_lowercase().trim().to_string();
+
+    let input_normalized = input.to_lowercase();
+    let candidates = prefilter.candidates(input_normalized.trim());
+
+    match_intent_over(input, triggers, candidates.into_iter())
+}
+
+fn match_intent_over(
+    input: &str,
+    triggers: &[Trigger],
+    indices: impl Iterator<Item = usize>,
+) -> ParseResult {
+    if triggers.is_empty() {
+        return ParseResult::None;
+    }
+
+    // Normalize input. `match_positions` is computed against the trimmed
+    // string (for a clean alignment), then shifted by `leading_trim_chars`
+    // so the offsets line up with `original_input`, which callers index
+    // into untrimmed.
+    let input_lower = input.to_lowercase();
+    let leading_trim_chars = input_lower.chars().take_while(|c| c.is_whitespace()).count();
+    let input_normalized = input_lower.trim().to_string();
     let input_normalized_str = input_normalized.as_str();
-    
-    // Score all triggers
-    let mut scored_matches: Vec<(IntentMatch, f64)> = Vec::with_capacity(triggers.len());
-    
-    for trigger in triggers {
+
+    // Score the candidate triggers
+    let mut scored_matches: Vec<(IntentMatch, f64)> = Vec::new();
+
+    for idx in indices {
+        let trigger = &triggers[idx];
+
         // Check exact pattern
         let pattern_score = calculate_similarity(input_normalized_str, &trigger.pattern);
-        
-        // Check aliases
+
+        // Check aliases, tracking whichever text (pattern or alias) is
+        // actually responsible for the best score so positions highlight
+        // the right thing.
         let mut best_score = pattern_score;
+        let mut best_text: &str = &trigger.pattern;
         for alias in &trigger.aliases {
             let alias_score = calculate_similarity(input_normalized_str, alias);
             if alias_score > best_score {
                 best_score = alias_score;
+                best_text = alias;
             }
         }
-        
+
         // Apply weight
         let final_score = best_score * trigger.weight;
-        
+
         if final_score >= MIN_CONFIDENCE {
-            let intent_match = IntentMatch::new(
+            let mut intent_match = IntentMatch::new(
                 trigger.intent_name.clone(),
                 trigger.provider_name.clone(),
                 final_score,
@@ -49,13 +201,17 @@ pub fn match_intent(input: &str, triggers: &[Trigger]) -> ParseResult {
                 input.to_string(),
                 "rule_based".to_string(),
             );
-            
+            intent_match.match_positions = match_positions(input_normalized_str, best_text)
+                .into_iter()
+                .map(|pos| pos + leading_trim_chars)
+                .collect();
+
             scored_matches.push((intent_match, final_score));
         }
     }
-    
+
     if scored_matches.is_empty() {
-        return ParseResult::None;
+        return no_match_with_suggestions(input_normalized_str, triggers);
     }
     
     // Sort by score (descending)
@@ -96,6 +252,43 @@ pub fn match_intent(input: &str, triggers: &[Trigger]) -> ParseResult {
     ParseResult::None
 }
 
+/// Build a `ParseResult` for the "nothing cleared MIN_CONFIDENCE" case:
+/// `Suggestions` if any trigger is close enough by Jaro-Winkler similarity
+/// to be worth a "did you mean ...?" prompt, otherwise plain `None`.
+fn no_match_with_suggestions(input_normalized: &str, triggers: &[Trigger]) -> ParseResult {
+    let mut candidates: Vec<Suggestion> = Vec::new();
+
+    for trigger in triggers {
+        let mut best_score = jaro_winkler(input_normalized, &trigger.pattern.to_lowercase());
+        for alias in &trigger.aliases {
+            let alias_score = jaro_winkler(input_normalized, &alias.to_lowercase());
+            if alias_score > best_score {
+                best_score = alias_score;
+            }
+        }
+
+        if best_score >= SUGGESTION_CONFIDENCE {
+            candidates.push(Suggestion {
+                intent_name: trigger.intent_name.clone(),
+                provider_name: trigger.provider_name.clone(),
+                trigger_pattern: trigger.pattern.clone(),
+                score: best_score,
+            });
+        }
+    }
+
+    if candidates.is_empty() {
+        return ParseResult::None;
+    }
+
+    // Sort ascending by score, then take the closest ones off the top end
+    // so the best suggestion comes first in the returned list.
+    candidates.sort_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(std::cmp::Ordering::Equal));
+    let top: Vec<Suggestion> = candidates.into_iter().rev().take(MAX_SUGGESTIONS).collect();
+
+    ParseResult::Suggestions(top)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,4 +331,120 @@ mod tests {
             _ => panic!("Expected no match"),
         }
     }
+
+    fn sample_triggers() -> Vec<Trigger> {
+        vec![
+            Trigger {
+                pattern: "open desktop".to_string(),
+                intent_name: "open_desktop".to_string(),
+                provider_name: "filesystem".to_string(),
+                weight: 1.0,
+                aliases: vec!["show desktop".to_string()],
+            },
+            Trigger {
+                pattern: "list processes".to_string(),
+                intent_name: "list_processes".to_string(),
+                provider_name: "system".to_string(),
+                weight: 1.0,
+                aliases: Vec::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_prefilter_keeps_trigger_with_short_token_always_candidate() {
+        // "cd" is below MIN_LITERAL_LEN and un-indexable, so a full scan on
+        // just "cd" would still score this trigger via token overlap - the
+        // prefilter must not be the one excluding it.
+        let triggers = vec![Trigger {
+            pattern: "cd projects".to_string(),
+            intent_name: "cd_projects".to_string(),
+            provider_name: "filesystem".to_string(),
+            weight: 1.0,
+            aliases: Vec::new(),
+        }];
+        let prefilter = TriggerPrefilter::build(&triggers);
+
+        let candidates = prefilter.candidates("cd");
+        assert!(candidates.contains(&0));
+    }
+
+    #[test]
+    fn test_prefilter_candidates_narrow_to_matching_literals() {
+        let triggers = sample_triggers();
+        let prefilter = TriggerPrefilter::build(&triggers);
+
+        let candidates = prefilter.candidates("open desktop");
+        assert!(candidates.contains(&0));
+        assert!(!candidates.contains(&1));
+    }
+
+    #[test]
+    fn test_match_reports_contiguous_positions_for_substring_match() {
+        let triggers = sample_triggers();
+
+        match match_intent("please open desktop now", &triggers) {
+            ParseResult::Match(m) => {
+                assert!(!m.match_positions.is_empty());
+                let positions = &m.match_positions;
+                let contiguous = positions.windows(2).all(|w| w[1] == w[0] + 1);
+                assert!(contiguous);
+            }
+            other => panic!("Expected match, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_match_positions_account_for_leading_whitespace() {
+        let triggers = sample_triggers();
+
+        match match_intent("  open desktop", &triggers) {
+            ParseResult::Match(m) => {
+                // "open desktop" starts at char index 2 in the untrimmed
+                // original_input; positions must be shifted to match.
+                assert_eq!(m.match_positions.first(), Some(&2));
+            }
+            other => panic!("Expected match, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_suggestions_on_close_typo() {
+        let triggers = sample_triggers();
+
+        match match_intent("opn dsktp", &triggers) {
+            ParseResult::Suggestions(suggestions) => {
+                assert!(!suggestions.is_empty());
+                assert_eq!(suggestions[0].intent_name, "open_desktop");
+            }
+            other => panic!("Expected suggestions, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_no_suggestions_for_unrelated_input() {
+        let triggers = sample_triggers();
+
+        match match_intent("zzz qqq xyz", &triggers) {
+            ParseResult::None => {}
+            other => panic!("Expected None, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_match_intent_prefiltered_matches_full_scan() {
+        let triggers = sample_triggers();
+        let prefilter = TriggerPrefilter::build(&triggers);
+
+        let direct = match_intent("list processes", &triggers);
+        let filtered = match_intent_prefiltered("list processes", &triggers, &prefilter);
+
+        match (direct, filtered) {
+            (ParseResult::Match(a), ParseResult::Match(b)) => {
+                assert_eq!(a.intent_name, b.intent_name);
+                assert!((a.confidence - b.confidence).abs() < 0.001);
+            }
+            other => panic!("Expected matching results, got {other:?}"),
+        }
+    }
 }
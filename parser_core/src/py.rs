@@ -3,9 +3,10 @@
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
 use crate::types::{Trigger, ParseResult};
-use crate::similarity::calculate_similarity;
-use crate::matcher::match_intent;
+use crate::similarity::{calculate_similarity, fuzzy_score};
+use crate::matcher::{match_intent_prefiltered, TriggerPrefilter};
 use crate::entities::EntityExtractor;
+use crate::linter::{lint_triggers, DiagnosticKind, Severity};
 use serde_json;
 
 /// Calculate similarity between two strings (Python function)
@@ -29,12 +30,22 @@ impl PySimilarityCalculator {
     fn calculate(&self, input: &str, pattern: &str) -> f64 {
         calculate_similarity(input, pattern)
     }
+
+    /// Calculate fzf-style fuzzy score, rewarding matches at word boundaries
+    /// and consecutive runs over a plain token/LCS overlap.
+    fn calculate_fuzzy(&self, input: &str, pattern: &str) -> f64 {
+        fuzzy_score(input, pattern)
+    }
 }
 
 /// Python wrapper for intent matcher
 #[pyclass]
 pub struct PyIntentMatcher {
     triggers: Vec<Trigger>,
+    // Rebuilt lazily on the next match_intent call after a mutation, rather
+    // than on every add_trigger, so registering many triggers in a row
+    // doesn't rebuild the automaton after each one.
+    prefilter: Option<TriggerPrefilter>,
 }
 
 #[pymethods]
@@ -43,9 +54,10 @@ impl PyIntentMatcher {
     fn new() -> Self {
         Self {
             triggers: Vec::new(),
+            prefilter: None,
         }
     }
-    
+
     /// Add a trigger pattern
     fn add_trigger(
         &mut self,
@@ -62,6 +74,7 @@ impl PyIntentMatcher {
             weight,
             aliases,
         });
+        self.prefilter = None;
     }
     
     /// Add triggers from Python list of dicts (deprecated - use add_trigger instead)
@@ -92,14 +105,18 @@ impl PyIntentMatcher {
             
             self.add_trigger(pattern, intent_name, provider_name, weight, aliases);
         }
-        
+
         Ok(())
     }
-    
+
     /// Match user input against triggers
-    fn match_intent<'py>(&self, input: &str, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
-        let result = match_intent(input, &self.triggers);
-        
+    fn match_intent<'py>(&mut self, input: &str, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        if self.prefilter.is_none() {
+            self.prefilter = Some(TriggerPrefilter::build(&self.triggers));
+        }
+        let prefilter = self.prefilter.as_ref().unwrap();
+        let result = match_intent_prefiltered(input, &self.triggers, prefilter);
+
         match result {
             ParseResult::Match(m) => {
                 let dict = PyDict::new_bound(py);
@@ -110,6 +127,7 @@ impl PyIntentMatcher {
                 dict.set_item("trigger_pattern", m.trigger_pattern)?;
                 dict.set_item("original_input", m.original_input)?;
                 dict.set_item("source", m.source)?;
+                dict.set_item("match_positions", m.match_positions.clone())?;
                 // Convert entities to Python list
                 let entities_json = serde_json::to_string(&m.entities)
                     .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Failed to serialize entities: {}", e)))?;
@@ -129,6 +147,7 @@ impl PyIntentMatcher {
                         m_dict.set_item("provider_name", &m.provider_name)?;
                         m_dict.set_item("confidence", m.confidence)?;
                         m_dict.set_item("trigger_pattern", &m.trigger_pattern)?;
+                        m_dict.set_item("match_positions", m.match_positions.clone())?;
                         Ok(m_dict)
                     })
                     .collect::<PyResult<Vec<_>>>()?;
@@ -141,12 +160,57 @@ impl PyIntentMatcher {
                 dict.set_item("type", "none")?;
                 Ok(dict)
             }
+            ParseResult::Suggestions(suggestions) => {
+                let dict = PyDict::new_bound(py);
+                dict.set_item("type", "suggestions")?;
+
+                let suggestions: Vec<Bound<'_, PyDict>> = suggestions
+                    .iter()
+                    .map(|s| -> PyResult<Bound<'_, PyDict>> {
+                        let s_dict = PyDict::new_bound(py);
+                        s_dict.set_item("intent_name", &s.intent_name)?;
+                        s_dict.set_item("provider_name", &s.provider_name)?;
+                        s_dict.set_item("trigger_pattern", &s.trigger_pattern)?;
+                        s_dict.set_item("score", s.score)?;
+                        Ok(s_dict)
+                    })
+                    .collect::<PyResult<Vec<_>>>()?;
+
+                dict.set_item("suggestions", suggestions)?;
+                Ok(dict)
+            }
         }
     }
     
     /// Clear all triggers
     fn clear(&mut self) {
         self.triggers.clear();
+        self.prefilter = None;
+    }
+
+    /// Lint the registered triggers for redundant, unreachable, and
+    /// overlapping-alias conflicts.
+    fn lint<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        lint_triggers(&self.triggers)
+            .iter()
+            .map(|d| -> PyResult<Bound<'py, PyDict>> {
+                let dict = PyDict::new_bound(py);
+                let kind = match d.kind {
+                    DiagnosticKind::Redundant => "redundant",
+                    DiagnosticKind::Unreachable => "unreachable",
+                    DiagnosticKind::OverlappingAlias => "overlapping_alias",
+                };
+                let severity = match d.severity {
+                    Severity::Warning => "warning",
+                    Severity::Error => "error",
+                };
+                dict.set_item("kind", kind)?;
+                dict.set_item("severity", severity)?;
+                dict.set_item("trigger_indices", d.trigger_indices.clone())?;
+                dict.set_item("message", &d.message)?;
+                Ok(dict)
+            })
+            .collect()
     }
     
     /// Get number of triggers
@@ -169,7 +233,24 @@ impl PyEntityExtractor {
             extractor: EntityExtractor::new(),
         }
     }
-    
+
+    /// Register a custom entity pattern that participates in the same
+    /// literal prefilter as the built-in patterns. `required_literals`
+    /// should be lowercase atoms, any one of which must be present in the
+    /// input for `pattern` to be run; pass an empty list for a pattern
+    /// with no distinguishing literal, which always runs.
+    fn add_pattern(
+        &mut self,
+        pattern: &str,
+        entity_type: String,
+        required_literals: Vec<String>,
+    ) -> PyResult<()> {
+        let regex = regex::Regex::new(pattern)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid regex pattern: {}", e)))?;
+        self.extractor.add_pattern(regex, entity_type, required_literals);
+        Ok(())
+    }
+
     /// Extract entities from text
     fn extract<'py>(&self, text: &str, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
         let entities = self.extractor.extract(text);
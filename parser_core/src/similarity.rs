@@ -4,6 +4,27 @@
 
 use ahash::AHashSet;
 
+/// Selects which scoring algorithm [`calculate_similarity_with_mode`] uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimilarityMode {
+    /// Token overlap + LCS ratio (the historical default).
+    TokenLcs,
+    /// fzf-style positional alignment, see [`fuzzy_score`].
+    Fuzzy,
+}
+
+/// Calculate similarity using an explicitly selected algorithm.
+///
+/// `calculate_similarity` keeps using [`SimilarityMode::TokenLcs`] for
+/// backward compatibility; callers that want positional-aware scoring
+/// (e.g. for `open desktop`-style shell queries) should pass `Fuzzy`.
+pub fn calculate_similarity_with_mode(input: &str, pattern: &str, mode: SimilarityMode) -> f64 {
+    match mode {
+        SimilarityMode::TokenLcs => calculate_similarity(input, pattern),
+        SimilarityMode::Fuzzy => fuzzy_score(input, pattern),
+    }
+}
+
 /// Calculate similarity score between input and pattern (0.0-1.0)
 ///
 /// Uses a multi-stage approach:
@@ -108,10 +129,309 @@ fn longest_common_subsequence(s1: &str, s2: &str) -> usize {
     prev[n]
 }
 
+// fzf/Smith-Waterman-style bonus tuning. Values are relative, not probabilities,
+// so they only matter in proportion to each other.
+const SCORE_MATCH: f64 = 16.0;
+const BONUS_BOUNDARY: f64 = 8.0;
+const BONUS_CONSECUTIVE: f64 = 12.0;
+const PENALTY_GAP_START: f64 = 3.0;
+const PENALTY_GAP_EXTENSION: f64 = 1.0;
+
+/// fzf-style fuzzy score between 0.0 and 1.0.
+///
+/// Performs a char-by-char subsequence alignment of `pattern` into `input`
+/// using two DP rows (best score so far, and a flag for whether that best
+/// alignment ends in a match, so the next char can extend the run), rewarding matches that land on word boundaries or
+/// continue a previous match, and penalizing skipped input characters.
+/// Unlike [`calculate_similarity`], this distinguishes matches by *where*
+/// they land, so `op desk` scores far higher against `open desktop` than
+/// a random subsequence of the same length.
+pub fn fuzzy_score(input: &str, pattern: &str) -> f64 {
+    if pattern.is_empty() {
+        return 0.0;
+    }
+    if input.is_empty() {
+        return 0.0;
+    }
+
+    let input_chars: Vec<char> = input.chars().collect();
+    let pattern_chars: Vec<char> = pattern.to_lowercase().chars().collect();
+    let input_lower: Vec<char> = input.to_lowercase().chars().collect();
+
+    let n = input_chars.len();
+    let m = pattern_chars.len();
+
+    // score[j] = best cumulative score aligning pattern[..=row] into input[..=j]
+    // consecutive[j] = 1.0 iff the best alignment at this cell ended in a
+    // match at input[j] (so the next row can extend the run), else 0.0
+    let mut prev_score = vec![0.0f64; n + 1];
+    let mut prev_consecutive = vec![0.0f64; n + 1];
+
+    for &pc in &pattern_chars {
+        let mut curr_score = vec![0.0f64; n + 1];
+        let mut curr_consecutive = vec![0.0f64; n + 1];
+        // curr_in_gap[idx] is true iff the best alignment carried into this
+        // column got there by skipping an input char rather than matching
+        // one, so the *next* column knows whether it's continuing an
+        // existing gap (cheap) or opening a new one (the one-time
+        // PENALTY_GAP_START cost).
+        let mut curr_in_gap = vec![false; n + 1];
+        let mut best_in_row = 0.0f64;
+
+        for j in 0..n {
+            let idx = j + 1;
+            let mut score_here = 0.0f64;
+            let mut consecutive_here = 0.0f64;
+            let mut matched = false;
+
+            if input_lower[j] == pc {
+                let boundary = boundary_bonus(&input_chars, j);
+                // prev_consecutive[j] flags whether the previous pattern
+                // row's best alignment ended with a match at input position
+                // j-1 (1-indexed: column j), i.e. this match would extend
+                // that run. It's 0 both for "no run" and at row 0 -
+                // row 0 can't extend a previous match.
+                let consecutive_bonus = if prev_consecutive[j] > 0.0 {
+                    BONUS_CONSECUTIVE
+                } else {
+                    0.0
+                };
+
+                // prev_score[j] is the best score aligning pattern[..row]
+                // into the first j input chars (0 at row 0, by construction).
+                let diag_score = prev_score[j];
+                let matched_score = diag_score + SCORE_MATCH + boundary.max(consecutive_bonus);
+
+                score_here = matched_score;
+                consecutive_here = 1.0;
+                matched = true;
+            }
+
+            // Carry forward the best score so far: a one-time
+            // PENALTY_GAP_START the first time a run of skipped chars
+            // opens, then the smaller PENALTY_GAP_EXTENSION per char for as
+            // long as it continues.
+            let gap_penalty = if best_in_row > 0.0 {
+                if curr_in_gap[j] {
+                    PENALTY_GAP_EXTENSION
+                } else {
+                    PENALTY_GAP_START
+                }
+            } else {
+                0.0
+            };
+            let carried = (curr_score[j] - gap_penalty).max(0.0);
+
+            if carried > score_here {
+                score_here = carried;
+                consecutive_here = 0.0;
+                matched = false;
+            }
+
+            best_in_row = best_in_row.max(score_here);
+            curr_score[idx] = score_here;
+            curr_consecutive[idx] = consecutive_here;
+            curr_in_gap[idx] = !matched;
+        }
+
+        prev_score = curr_score;
+        prev_consecutive = curr_consecutive;
+    }
+
+    let best = prev_score.into_iter().fold(0.0f64, f64::max);
+    let max_possible = (m as f64) * (SCORE_MATCH + BONUS_CONSECUTIVE);
+
+    if max_possible <= 0.0 {
+        return 0.0;
+    }
+
+    (best / max_possible).clamp(0.0, 1.0)
+}
+
+/// Bonus for a matched char landing on a "meaningful" boundary: start of
+/// string, right after a separator, or a camelCase transition.
+fn boundary_bonus(input_chars: &[char], idx: usize) -> f64 {
+    if idx == 0 {
+        return BONUS_BOUNDARY;
+    }
+
+    let prev = input_chars[idx - 1];
+    let curr = input_chars[idx];
+
+    if matches!(prev, ' ' | '_' | '-' | '/' | '.') {
+        return BONUS_BOUNDARY;
+    }
+
+    if prev.is_lowercase() && curr.is_uppercase() {
+        return BONUS_BOUNDARY;
+    }
+
+    0.0
+}
+
+/// Char offsets into `input` that best explain its similarity to `pattern`,
+/// for highlighting matched characters in a shell UI.
+///
+/// Mirrors the scoring `calculate_similarity` does: the substring fast
+/// paths (exact match, case-insensitive match) report a single contiguous
+/// range, and the sequence-similarity fallback rebuilds the LCS DP used for
+/// scoring as a full table and backtracks it to recover which `input` chars
+/// actually line up with `pattern`.
+pub fn match_positions(input: &str, pattern: &str) -> Vec<usize> {
+    if pattern.is_empty() || input.is_empty() {
+        return Vec::new();
+    }
+
+    let input_lower = input.to_lowercase();
+    let pattern_lower = pattern.to_lowercase();
+
+    if let Some(byte_start) = input_lower.find(&pattern_lower) {
+        let char_start = input_lower[..byte_start].chars().count();
+        let char_len = pattern_lower.chars().count();
+        return (char_start..char_start + char_len).collect();
+    }
+
+    lcs_positions(&input_lower, &pattern_lower)
+}
+
+/// Backtrack a full (non-space-optimized) LCS DP table to recover which
+/// `s1` char indices participate in the longest common subsequence with
+/// `s2`.
+fn lcs_positions(s1: &str, s2: &str) -> Vec<usize> {
+    let s1_chars: Vec<char> = s1.chars().collect();
+    let s2_chars: Vec<char> = s2.chars().collect();
+
+    let m = s1_chars.len();
+    let n = s2_chars.len();
+
+    if m == 0 || n == 0 {
+        return Vec::new();
+    }
+
+    let mut dp = vec![vec![0usize; n + 1]; m + 1];
+    for i in 1..=m {
+        for j in 1..=n {
+            dp[i][j] = if s1_chars[i - 1] == s2_chars[j - 1] {
+                dp[i - 1][j - 1] + 1
+            } else {
+                dp[i - 1][j].max(dp[i][j - 1])
+            };
+        }
+    }
+
+    let mut positions = Vec::new();
+    let (mut i, mut j) = (m, n);
+    while i > 0 && j > 0 {
+        if s1_chars[i - 1] == s2_chars[j - 1] {
+            positions.push(i - 1);
+            i -= 1;
+            j -= 1;
+        } else if dp[i - 1][j] >= dp[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+
+    positions.reverse();
+    positions
+}
+
+/// Winkler prefix boost only considers up to this many leading chars.
+const JARO_WINKLER_MAX_PREFIX: usize = 4;
+/// Standard Winkler prefix scaling factor.
+const JARO_WINKLER_PREFIX_WEIGHT: f64 = 0.1;
+
+/// Jaro-Winkler similarity between `s1` and `s2`, in 0.0-1.0.
+///
+/// Used for "did you mean ...?" suggestions: unlike [`calculate_similarity`]
+/// (tuned for substring/token containment) or [`fuzzy_score`] (tuned for
+/// subsequence alignment), Jaro-Winkler rewards strings that are close
+/// edit-wise overall, which is a better fit for near-miss typo suggestions.
+pub fn jaro_winkler(s1: &str, s2: &str) -> f64 {
+    let jaro = jaro_similarity(s1, s2);
+    if jaro <= 0.0 {
+        return 0.0;
+    }
+
+    let s1_chars: Vec<char> = s1.chars().collect();
+    let s2_chars: Vec<char> = s2.chars().collect();
+
+    let prefix_len = s1_chars
+        .iter()
+        .zip(s2_chars.iter())
+        .take(JARO_WINKLER_MAX_PREFIX)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    jaro + JARO_WINKLER_PREFIX_WEIGHT * prefix_len as f64 * (1.0 - jaro)
+}
+
+fn jaro_similarity(s1: &str, s2: &str) -> f64 {
+    let s1_chars: Vec<char> = s1.chars().collect();
+    let s2_chars: Vec<char> = s2.chars().collect();
+
+    let len1 = s1_chars.len();
+    let len2 = s2_chars.len();
+
+    if len1 == 0 && len2 == 0 {
+        return 1.0;
+    }
+    if len1 == 0 || len2 == 0 {
+        return 0.0;
+    }
+
+    let match_window = (len1.max(len2) / 2).saturating_sub(1);
+
+    let mut s1_matched = vec![false; len1];
+    let mut s2_matched = vec![false; len2];
+    let mut matches = 0usize;
+
+    for i in 0..len1 {
+        let lo = i.saturating_sub(match_window);
+        let hi = (i + match_window + 1).min(len2);
+        if lo >= hi {
+            continue;
+        }
+        for j in lo..hi {
+            if s2_matched[j] || s1_chars[i] != s2_chars[j] {
+                continue;
+            }
+            s1_matched[i] = true;
+            s2_matched[j] = true;
+            matches += 1;
+            break;
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let mut transpositions = 0usize;
+    let mut s2_idx = 0usize;
+    for i in 0..len1 {
+        if !s1_matched[i] {
+            continue;
+        }
+        while !s2_matched[s2_idx] {
+            s2_idx += 1;
+        }
+        if s1_chars[i] != s2_chars[s2_idx] {
+            transpositions += 1;
+        }
+        s2_idx += 1;
+    }
+    let transpositions = transpositions / 2;
+
+    let m = matches as f64;
+    (m / len1 as f64 + m / len2 as f64 + (m - transpositions as f64) / m) / 3.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_exact_match() {
         assert!((calculate_similarity("open desktop", "open desktop") - 1.0).abs() < 0.001);
@@ -133,4 +453,98 @@ mod tests {
         let score = calculate_similarity("hello world", "open desktop");
         assert!(score < 0.3);
     }
+
+    #[test]
+    fn test_fuzzy_score_exact_match() {
+        // Not exactly 1.0: the first matched char can only earn the
+        // boundary bonus, not the (larger) consecutive-match bonus.
+        assert!(fuzzy_score("open desktop", "open desktop") > 0.95);
+    }
+
+    #[test]
+    fn test_fuzzy_score_rewards_boundary_matches() {
+        // Same contiguous match, only differing in whether it starts right
+        // after a word boundary.
+        let boundary = fuzzy_score("open desktop", "desktop");
+        let mid_word = fuzzy_score("xxxdesktop", "desktop");
+        assert!(boundary > mid_word);
+    }
+
+    #[test]
+    fn test_fuzzy_score_gap_extension_cheaper_than_gap_start() {
+        // Widening a gap from 1 skipped char to 3 should only cost two
+        // extra PENALTY_GAP_EXTENSION charges (2.0 raw), not two extra
+        // full (PENALTY_GAP_START + PENALTY_GAP_EXTENSION) charges (8.0
+        // raw) as if every skipped char reopened the gap.
+        let short_gap = fuzzy_score("a_b", "ab");
+        let long_gap = fuzzy_score("a___b", "ab");
+        let max_possible = 2.0 * (SCORE_MATCH + BONUS_CONSECUTIVE);
+
+        let diff = (short_gap - long_gap) * max_possible;
+        assert!((diff - 2.0 * PENALTY_GAP_EXTENSION).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_fuzzy_score_distinguishes_alignment() {
+        // "op desk" should align cleanly onto "open desktop"; a scattered
+        // subsequence like "od esk" should score lower.
+        let clean = fuzzy_score("open desktop", "op desk");
+        let scattered = fuzzy_score("open desktop", "od esk");
+        assert!(clean > scattered);
+    }
+
+    #[test]
+    fn test_fuzzy_score_no_match() {
+        let score = fuzzy_score("open desktop", "zzz");
+        assert_eq!(score, 0.0);
+    }
+
+    #[test]
+    fn test_fuzzy_score_empty_pattern() {
+        assert_eq!(fuzzy_score("open desktop", ""), 0.0);
+    }
+
+    #[test]
+    fn test_jaro_winkler_identical() {
+        assert!((jaro_winkler("martha", "martha") - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_jaro_winkler_classic_example() {
+        // Canonical reference value for this pair.
+        let score = jaro_winkler("martha", "marhta");
+        assert!((score - 0.961).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_jaro_winkler_prefix_boost() {
+        let base_jaro = jaro_similarity("dwayne", "duane");
+        let winkler = jaro_winkler("dwayne", "duane");
+        assert!(winkler > base_jaro);
+    }
+
+    #[test]
+    fn test_jaro_winkler_empty_strings() {
+        assert_eq!(jaro_winkler("", ""), 1.0);
+        assert_eq!(jaro_winkler("abc", ""), 0.0);
+    }
+
+    #[test]
+    fn test_match_positions_substring_is_contiguous() {
+        let positions = match_positions("open desktop folder", "desktop");
+        assert_eq!(positions, vec![5, 6, 7, 8, 9, 10, 11]);
+    }
+
+    #[test]
+    fn test_match_positions_sequence_fallback_bounded_by_shorter_len() {
+        let positions = match_positions("opn dsktp", "open desktop");
+        // LCS can't exceed the shorter string's length.
+        assert!(positions.len() <= "opn dsktp".chars().count());
+        assert!(!positions.is_empty());
+    }
+
+    #[test]
+    fn test_match_positions_empty_pattern() {
+        assert!(match_positions("open desktop", "").is_empty());
+    }
 }
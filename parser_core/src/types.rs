@@ -12,6 +12,10 @@ pub struct IntentMatch {
     pub original_input: String,
     pub entities: Vec<Entity>,
     pub source: String, // "rule_based" or "llm"
+    /// Char offsets into `original_input` (lowercased) that the matcher
+    /// identified as causing the match, for highlighting in a shell UI.
+    /// Empty when the matcher couldn't recover a meaningful alignment.
+    pub match_positions: Vec<usize>,
 }
 
 /// Represents an extracted entity from user input
@@ -31,6 +35,16 @@ pub struct AmbiguousMatch {
     pub suggestions: Vec<IntentMatch>,
 }
 
+/// A near-miss trigger offered as a "did you mean ...?" suggestion when no
+/// trigger cleared `MIN_CONFIDENCE`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub intent_name: String,
+    pub provider_name: String,
+    pub trigger_pattern: String,
+    pub score: f64,
+}
+
 /// Represents a trigger pattern for intent matching
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trigger {
@@ -51,6 +65,10 @@ pub enum ParseResult {
     Ambiguous(AmbiguousMatch),
     #[serde(rename = "none")]
     None,
+    /// No trigger cleared `MIN_CONFIDENCE`, but some came close enough to
+    /// suggest as a "did you mean ...?" prompt.
+    #[serde(rename = "suggestions")]
+    Suggestions(Vec<Suggestion>),
 }
 
 impl IntentMatch {
@@ -70,6 +88,7 @@ impl IntentMatch {
             original_input,
             entities: Vec::new(),
             source,
+            match_positions: Vec::new(),
         }
     }
 }